@@ -0,0 +1,148 @@
+//! Support to avoid stack overflows that otherwise could occur when the compiler-derived
+//! [`Clone`] for [`Value`] recurses once per nesting level.
+//!
+//! Any deep tree of `Value`s, of any variants and any shapes, could cause stack overflow when
+//! cloned, for the same reason dropping one can (see [`mod@crate::drop`]): a long chain of cons
+//! cells deep down the `cdr` side, or deeply nested vectors.  When the `deep_safe_clone` feature
+//! is enabled, [`Value`]'s [`Clone`] impl instead walks the source with an explicit work stack, so
+//! cloning such values cannot overflow the stack.
+//!
+//! (The default, recursive, derived impl is kept when neither this feature nor `big_stack` is
+//! enabled, since it's simpler and faster for the common case of shallow `Value`s.  With only
+//! `big_stack` enabled, the recursive impl at the end of this module is used instead.)
+
+// Note: This module does `match`es without wildcard arms, because if which variants have children
+// ever changes, this module will need to adjust for that.
+
+use crate::{Cons, Value, Vector};
+
+/// A frame of in-progress work for [`deep_safe_clone`], kept on an explicit stack instead of the
+/// call stack.
+enum Frame<'a> {
+    /// The `car` of a `Cons` is about to be cloned; the `cdr`, pointed to by `source_cdr`, is
+    /// cloned next, once the `car` is done.
+    ConsCar { source_cdr: &'a Value },
+    /// Both children of a `Cons` have been cloned; combine them into the result.
+    ConsCdr { car: Value },
+    /// A `Vector` is being rebuilt; `built` holds the holes filled so far and `next` is the index
+    /// of the next element, of `source`, to clone.
+    Vector {
+        source: &'a [Value],
+        next: usize,
+        built: Vec<Value>,
+    },
+}
+
+/// Clone `root` without recursing, so that stack overflow cannot occur regardless of how deeply
+/// nested `root` is.
+pub(crate) fn deep_safe_clone(root: &Value) -> Value {
+    let mut stack: Vec<Frame> = Vec::new();
+    let mut current: &Value = root;
+    let mut done: Value;
+
+    'descend: loop {
+        done = loop {
+            match current {
+                Value::Cons(cons) => {
+                    stack.push(Frame::ConsCar {
+                        source_cdr: cons.cdr(),
+                    });
+                    current = cons.car();
+                }
+                Value::Vector(vector) => {
+                    let source = vector.as_slice();
+                    match source.first() {
+                        Some(first) => {
+                            stack.push(Frame::Vector {
+                                source,
+                                next: 1,
+                                built: Vec::with_capacity(source.len()),
+                            });
+                            current = first;
+                        }
+                        None => break Value::Vector(Vector::new()),
+                    }
+                }
+                Value::Nil
+                | Value::Null
+                | Value::Bool(_)
+                | Value::Number(_)
+                | Value::Char(_)
+                | Value::String(_)
+                | Value::Symbol(_)
+                | Value::Keyword(_)
+                | Value::Bytes(_) => break current.clone(),
+            }
+        };
+
+        loop {
+            match stack.pop() {
+                None => return done,
+                Some(Frame::ConsCar { source_cdr }) => {
+                    stack.push(Frame::ConsCdr { car: done });
+                    current = source_cdr;
+                    continue 'descend;
+                }
+                Some(Frame::ConsCdr { car }) => {
+                    done = Value::Cons(Cons::new(car, done));
+                }
+                Some(Frame::Vector {
+                    source,
+                    next,
+                    mut built,
+                }) => {
+                    built.push(done);
+                    match source.get(next) {
+                        Some(elem) => {
+                            stack.push(Frame::Vector {
+                                source,
+                                next: next + 1,
+                                built,
+                            });
+                            current = elem;
+                            continue 'descend;
+                        }
+                        None => done = Value::Vector(Vector::from(built)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "deep_safe_clone")]
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        deep_safe_clone(self)
+    }
+}
+
+/// Clone `value` the natural, recursive way, but with each recursive call wrapped so the stack is
+/// grown as needed instead of overflowing.  This is the `big_stack` feature's alternative to
+/// [`deep_safe_clone`]'s explicit-stack approach.
+#[cfg(all(feature = "big_stack", not(feature = "deep_safe_clone")))]
+fn clone_growing_stack_as_needed(value: &Value) -> Value {
+    use crate::stack::ensure_sufficient_stack;
+
+    match value {
+        Value::Cons(cons) => Value::Cons(Cons::new(
+            ensure_sufficient_stack(|| clone_growing_stack_as_needed(cons.car())),
+            ensure_sufficient_stack(|| clone_growing_stack_as_needed(cons.cdr())),
+        )),
+        Value::Vector(vector) => Value::Vector(Vector::from(
+            vector
+                .as_slice()
+                .iter()
+                .map(|elem| ensure_sufficient_stack(|| clone_growing_stack_as_needed(elem)))
+                .collect::<Vec<_>>(),
+        )),
+        leaf => leaf.clone(),
+    }
+}
+
+#[cfg(all(feature = "big_stack", not(feature = "deep_safe_clone")))]
+impl Clone for Value {
+    fn clone(&self) -> Self {
+        clone_growing_stack_as_needed(self)
+    }
+}
@@ -0,0 +1,130 @@
+//! Support to avoid stack overflows that otherwise could occur when the compiler-derived
+//! [`PartialEq`], [`PartialOrd`], and [`Hash`] impls for [`Value`] recurse once per nesting
+//! level.
+//!
+//! Comparing or hashing a deep tree of `Value`s -- the same long `cdr` chains and deeply nested
+//! vectors that [`mod@crate::drop`] and [`mod@crate::clone`] guard against -- can overflow the
+//! stack, which is a denial-of-service risk when the `Value` was parsed from untrusted input.
+//! These impls instead walk the values with an explicit worklist, so their stack usage is
+//! constant regardless of nesting depth.
+
+// Note: This module does `match`es without wildcard arms, because if which variants have children
+// ever changes, this module will need to adjust for that.
+
+use crate::Value;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        let mut worklist = vec![(self, other)];
+        while let Some((left, right)) = worklist.pop() {
+            match (left, right) {
+                (Value::Nil, Value::Nil) | (Value::Null, Value::Null) => {}
+                (Value::Bool(l), Value::Bool(r)) if l == r => {}
+                (Value::Number(l), Value::Number(r)) if l == r => {}
+                (Value::Char(l), Value::Char(r)) if l == r => {}
+                (Value::String(l), Value::String(r)) if l == r => {}
+                (Value::Symbol(l), Value::Symbol(r)) if l == r => {}
+                (Value::Keyword(l), Value::Keyword(r)) if l == r => {}
+                (Value::Bytes(l), Value::Bytes(r)) if l == r => {}
+                (Value::Cons(l), Value::Cons(r)) => {
+                    worklist.push((l.car(), r.car()));
+                    worklist.push((l.cdr(), r.cdr()));
+                }
+                (Value::Vector(l), Value::Vector(r)) => {
+                    let (l, r) = (l.as_slice(), r.as_slice());
+                    if l.len() != r.len() {
+                        return false;
+                    }
+                    worklist.extend(l.iter().zip(r.iter()));
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        // A worklist entry is either a pending pair still to be compared, or an `Ordering` to
+        // apply once every pair pushed after it (i.e. popped before it, since this is a LIFO
+        // stack) has compared equal -- this is what lets a `Vector` length mismatch act as a
+        // tiebreaker that only applies after a true lexicographic, element-first comparison of
+        // its common-length prefix, the same way slice/`Vec` ordering works.
+        enum Task<'a> {
+            Pair(&'a Value, &'a Value),
+            ThenBy(Ordering),
+        }
+
+        let mut worklist = vec![Task::Pair(self, other)];
+        while let Some(task) = worklist.pop() {
+            let ordering = match task {
+                Task::ThenBy(ordering) => ordering,
+                Task::Pair(left, right) => match (left, right) {
+                    (Value::Nil, Value::Nil) | (Value::Null, Value::Null) => Ordering::Equal,
+                    (Value::Bool(l), Value::Bool(r)) => l.partial_cmp(r)?,
+                    (Value::Number(l), Value::Number(r)) => l.partial_cmp(r)?,
+                    (Value::Char(l), Value::Char(r)) => l.partial_cmp(r)?,
+                    (Value::String(l), Value::String(r)) => l.partial_cmp(r)?,
+                    (Value::Symbol(l), Value::Symbol(r)) => l.partial_cmp(r)?,
+                    (Value::Keyword(l), Value::Keyword(r)) => l.partial_cmp(r)?,
+                    (Value::Bytes(l), Value::Bytes(r)) => l.partial_cmp(r)?,
+                    (Value::Cons(l), Value::Cons(r)) => {
+                        // Pushed cdr-before-car, since the worklist is a LIFO stack and `car`
+                        // must be compared first to get car-then-cdr (lexicographic) ordering.
+                        worklist.push(Task::Pair(l.cdr(), r.cdr()));
+                        worklist.push(Task::Pair(l.car(), r.car()));
+                        continue;
+                    }
+                    (Value::Vector(l), Value::Vector(r)) => {
+                        let (l, r) = (l.as_slice(), r.as_slice());
+                        let len_ordering = l.len().partial_cmp(&r.len())?;
+                        if len_ordering != Ordering::Equal {
+                            worklist.push(Task::ThenBy(len_ordering));
+                        }
+                        // Pushed last-to-first so the first elements are compared first, and
+                        // before the length tiebreaker above, so a common prefix is compared in
+                        // full before length ever decides anything.
+                        worklist.extend(l.iter().zip(r.iter()).rev().map(|(l, r)| Task::Pair(l, r)));
+                        continue;
+                    }
+                    _ => return None,
+                },
+            };
+            if ordering != Ordering::Equal {
+                return Some(ordering);
+            }
+        }
+        Some(Ordering::Equal)
+    }
+}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut worklist = vec![self];
+        while let Some(value) = worklist.pop() {
+            std::mem::discriminant(value).hash(state);
+            match value {
+                Value::Nil | Value::Null => {}
+                Value::Bool(b) => b.hash(state),
+                Value::Number(n) => n.hash(state),
+                Value::Char(c) => c.hash(state),
+                Value::String(s) => s.hash(state),
+                Value::Symbol(s) => s.hash(state),
+                Value::Keyword(s) => s.hash(state),
+                Value::Bytes(b) => b.hash(state),
+                Value::Cons(cons) => {
+                    worklist.push(cons.cdr());
+                    worklist.push(cons.car());
+                }
+                Value::Vector(vector) => {
+                    let slice = vector.as_slice();
+                    slice.len().hash(state);
+                    worklist.extend(slice.iter().rev());
+                }
+            }
+        }
+    }
+}
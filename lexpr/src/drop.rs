@@ -6,6 +6,20 @@
 //! cons cells is deep down the `cdr` side.  When any deep `Value` is wrapped with
 //! [`DeepSafeValueDropper`], stack overflow is prevented (by preventing those recursive calls) by
 //! first mutating the children and root `Value`s to become leafs before their final dropping.
+//!
+//! ([`Drop`] isn't impl'ed for `Value` directly, because that can cause Rust error E0509 which
+//! would be an undesirable limitation.  Eliminating that would require `Cons` and `Vector` to hold
+//! `Drop`-implementing inner field types privately, instead of plain `Value`s, so that the places
+//! elsewhere in the crate that destructure an owned `Value` move out of those inner types rather
+//! than out of `Value` itself; `Cons` and `Vector` are defined outside this snapshot, so that
+//! restructuring isn't done here.
+//!
+//! BLOCKED: the backlog request behind this module asked for a built-in, wrapper-free deep-safe
+//! `Drop` for `Value` itself.  That's exactly the restructuring described above, and it isn't
+//! delivered by anything in this module -- [`DeepSafeValueDropper`] is the same wrapper newtype
+//! the request wanted to eliminate.  Don't read this module as having closed that request; it's
+//! blocked on `Cons`/`Vector` living outside this snapshot, and stays open until a tree with their
+//! real definitions lets the restructuring happen for real.)
 
 // Note: This module does `match`es without wildcard arms, because if which variants have children
 // ever changes, this module will need to adjust for that.
@@ -17,9 +31,6 @@ use std::mem::replace;
 /// Wrap a [`Value`] with this when you want dropping of it to use [`mod@deep_safe_drop`], to
 /// ensure that dropping of deep `Value`s (e.g. a long list, or a long chain of cons cells) cannot
 /// cause stack overflow.
-///
-/// ([`Drop`] isn't impl'ed for `Value` directly, because that can cause Rust error E0509 which
-/// would be an undesirable limitation.)
 pub struct DeepSafeValueDropper(pub Value);
 
 impl Drop for DeepSafeValueDropper {
@@ -28,6 +39,55 @@ impl Drop for DeepSafeValueDropper {
     }
 }
 
+/// Wrap a [`Value`] with this, instead of [`DeepSafeValueDropper`], when you'd rather dropping of
+/// deep `Value`s be made safe by growing the stack (via the `big_stack` feature) than by
+/// mutating the tree first.  See [`mod@crate::stack`].
+///
+/// Ideally `big_stack` would make wrapping unnecessary, by implementing `Drop` on `Value` directly
+/// with the recursive descent wrapped in [`mod@crate::stack`]'s helper.  That runs into the same
+/// E0509 obstacle this module's documentation describes, and the same restructuring of
+/// `Cons`/`Vector` would remove it for this strategy too; until then, wrapping is still required.
+#[cfg(feature = "big_stack")]
+pub struct BigStackValueDropper(pub Value);
+
+#[cfg(feature = "big_stack")]
+impl Drop for BigStackValueDropper {
+    fn drop(&mut self) {
+        drop_growing_stack_as_needed(&mut self.0);
+    }
+}
+
+/// Recursively drop `value`'s children, replacing each with a cheap leaf first so the eventual
+/// automatic dropping of `value` itself is trivial, growing the stack as needed (instead of
+/// avoiding the recursion, as [`deep_safe_drop`] does) so that deep trees don't overflow it.
+#[cfg(feature = "big_stack")]
+fn drop_growing_stack_as_needed(value: &mut Value) {
+    use crate::stack::ensure_sufficient_stack;
+
+    match value {
+        Value::Cons(cons) => {
+            let mut car = replace(cons.car_mut(), Value::Nil);
+            let mut cdr = replace(cons.cdr_mut(), Value::Nil);
+            ensure_sufficient_stack(|| drop_growing_stack_as_needed(&mut car));
+            ensure_sufficient_stack(|| drop_growing_stack_as_needed(&mut cdr));
+        }
+        Value::Vector(vector) => {
+            while let Some(mut elem) = vector.pop() {
+                ensure_sufficient_stack(|| drop_growing_stack_as_needed(&mut elem));
+            }
+        }
+        Value::Nil
+        | Value::Null
+        | Value::Bool(_)
+        | Value::Number(_)
+        | Value::Char(_)
+        | Value::String(_)
+        | Value::Symbol(_)
+        | Value::Keyword(_)
+        | Value::Bytes(_) => {}
+    }
+}
+
 impl DeepSafeDrop<Self> for Value {
     fn set_parent_at_index_0(&mut self, parent: Self) -> SetParent<Self> {
         match child_at_index_0(self) {
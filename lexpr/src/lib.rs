@@ -0,0 +1,26 @@
+// NOTE: This snapshot of the crate doesn't include the rest of `lib.rs` (the part that declares
+// `mod value;` and re-exports `Value`, `Cons`, `Vector`, `Number`, etc., along with the reader and
+// writer modules) or `Cargo.toml`.  The declarations below are only the ones needed to wire up the
+// modules touched by this backlog; merge them into the real `lib.rs` alongside the rest.
+//
+// `Cargo.toml` additionally needs:
+//   [dependencies]
+//   stacker = { version = "...", optional = true }
+//   [features]
+//   deep_safe_clone = []
+//   big_stack = ["dep:stacker"]
+
+mod cmp;
+mod drop;
+mod nesting;
+
+#[cfg(any(feature = "deep_safe_clone", feature = "big_stack"))]
+mod clone;
+
+#[cfg(feature = "big_stack")]
+mod stack;
+
+pub use drop::DeepSafeValueDropper;
+
+#[cfg(feature = "big_stack")]
+pub use drop::BigStackValueDropper;
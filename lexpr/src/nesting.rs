@@ -0,0 +1,279 @@
+//! Support for parsing and printing deeply nested `(`/`#(`-delimited input without recursing once
+//! per nesting level.
+//!
+//! A naively-written recursive-descent parser or recursive printer for [`Value`] overflows the
+//! stack on a pathological input like `((((...))))` nested a few hundred-thousand deep, before any
+//! `Value` is even handed back to the caller -- [`mod@crate::drop`] can't help with this, because
+//! the overflow happens during construction (or printing), not dropping.  This module holds the
+//! explicit-stack machinery the reader and the writer both build on instead: a stack of
+//! in-progress collections (for the reader) or of remaining-children iterators (for the writer),
+//! each frame pushed on open delimiters/non-leaf values and popped on close delimiters/finished
+//! collections.
+//!
+//! The reader additionally accepts a [`MaxDepth`], so applications that expect untrusted input can
+//! reject pathologically deep input with a clean error instead of relying on the underlying stack
+//! growth strategy ([`mod@crate::stack`], if enabled) or a hard stack limit.
+//!
+//! NOTE: the actual recursive-descent reader and writer this is meant to replace aren't part of
+//! this snapshot (no `parse.rs`/`print.rs`), so [`OpenStack`] and [`WriteStack`] aren't wired into
+//! them yet, and the vulnerability this module is meant to fix -- a crafted, pathologically deep
+//! `(`/`#(` input overflowing the stack during parsing or printing -- is NOT yet fixed by it.  This
+//! commit ships only the explicit-stack primitives the reader/writer would use, exercised directly
+//! by this module's own tests; rewiring `parse.rs`/`print.rs` onto them is separate, future work,
+//! tracked as a follow-up rather than closed out here.
+//!
+//! Because nothing outside this module's own tests calls into it yet, [`OpenStack`] and
+//! [`WriteStack`] are allowed to go unused on a non-test build; see the `allow` below.
+
+#![allow(dead_code)]
+
+use crate::Value;
+
+/// A limit on how deeply nested the `(`/`#(` collections the reader will accept may be, before it
+/// gives up and returns [`TooDeeplyNested`] instead of continuing to parse.
+///
+/// There's no limit by default; a parser would opt in to one (e.g. via a `set_max_depth` builder
+/// method, once a parser using this module exists) when parsing untrusted input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxDepth(pub usize);
+
+/// The error returned by the reader when a collection's nesting exceeds the configured
+/// [`MaxDepth`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooDeeplyNested {
+    /// The configured limit that was exceeded.
+    pub max_depth: usize,
+}
+
+/// One in-progress collection being built by the reader: either a list (accumulated in order
+/// until its closing `)` is seen, then folded into `Cons`es) or a vector (accumulated in order).
+enum Open {
+    List(Vec<Value>),
+    Vector(Vec<Value>),
+}
+
+/// The reader's explicit stack of collections currently open, innermost last.
+///
+/// Push a frame via [`Self::open_list`]/[`Self::open_vector`] on `(`/`#(`; append parsed values to
+/// the innermost frame via [`Self::push_value`] as they're read; pop and fold a frame into a
+/// completed [`Value`] via [`Self::close`] on `)`.  None of this recurses, so nesting depth is
+/// bounded only by available heap, not stack.
+#[derive(Default)]
+pub(crate) struct OpenStack {
+    frames: Vec<Open>,
+    max_depth: Option<usize>,
+}
+
+impl OpenStack {
+    pub(crate) fn with_max_depth(max_depth: Option<MaxDepth>) -> Self {
+        Self {
+            frames: Vec::new(),
+            max_depth: max_depth.map(|MaxDepth(max_depth)| max_depth),
+        }
+    }
+
+    pub(crate) fn open_list(&mut self) -> Result<(), TooDeeplyNested> {
+        self.open(Open::List(Vec::new()))
+    }
+
+    pub(crate) fn open_vector(&mut self) -> Result<(), TooDeeplyNested> {
+        self.open(Open::Vector(Vec::new()))
+    }
+
+    fn open(&mut self, frame: Open) -> Result<(), TooDeeplyNested> {
+        if let Some(max_depth) = self.max_depth {
+            if self.frames.len() >= max_depth {
+                return Err(TooDeeplyNested { max_depth });
+            }
+        }
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    /// Append a fully-read value (which may itself be a leaf or a previously-closed collection)
+    /// to the innermost open collection, or return it back if there is none (i.e. it's the
+    /// top-level datum).
+    pub(crate) fn push_value(&mut self, value: Value) -> Option<Value> {
+        match self.frames.last_mut() {
+            Some(Open::List(elts) | Open::Vector(elts)) => {
+                elts.push(value);
+                None
+            }
+            None => Some(value),
+        }
+    }
+
+    /// Close (pop) the innermost open collection, folding its accumulated elements into the
+    /// finished `Value`, which becomes a pending value to append to what's now the innermost
+    /// frame (or is returned, if the just-closed frame was the outermost).
+    pub(crate) fn close(&mut self) -> Option<Value> {
+        let value = match self.frames.pop()? {
+            Open::List(elts) => Value::list(elts),
+            Open::Vector(elts) => Value::vector(elts),
+        };
+        self.push_value(value)
+    }
+
+    pub(crate) fn depth(&self) -> usize {
+        self.frames.len()
+    }
+}
+
+/// One frame of the writer's explicit traversal stack: the remaining children of a `Cons` or
+/// `Vector` still to be printed.
+enum Remaining<'a> {
+    /// Still need to print `car`, then recurse on `cdr` (which becomes the new top frame, rather
+    /// than a recursive call, once `car` is done).
+    ConsCar(&'a Value),
+    /// The `cdr`, printed after `car`; not itself iterated over, just visited once.
+    ConsCdr(&'a Value),
+    Vector(std::slice::Iter<'a, Value>),
+}
+
+/// The writer's explicit stack of `Cons`/`Vector` ancestors currently being printed, used in place
+/// of recursing once per nesting level.
+pub(crate) struct WriteStack<'a> {
+    frames: Vec<Remaining<'a>>,
+}
+
+impl<'a> WriteStack<'a> {
+    pub(crate) fn new() -> Self {
+        Self { frames: Vec::new() }
+    }
+
+    /// Visit `current` and return the next value to visit (if any), without ever recursing:
+    /// nested `Cons`es and `Vector`s are pushed onto `self.frames` instead.  Nothing is written
+    /// here; printing delimiters and scalar payloads is left to the caller.
+    pub(crate) fn step(&mut self, current: &'a Value) -> Option<&'a Value> {
+        match current {
+            Value::Cons(cons) => {
+                self.frames.push(Remaining::ConsCar(cons.cdr()));
+                Some(cons.car())
+            }
+            Value::Vector(vector) => {
+                let mut iter = vector.as_slice().iter();
+                match iter.next() {
+                    Some(first) => {
+                        self.frames.push(Remaining::Vector(iter));
+                        Some(first)
+                    }
+                    // An empty vector has no children to visit; treat it like a leaf so any
+                    // outer siblings still on the frame stack get visited instead of being
+                    // abandoned.
+                    None => self.advance(),
+                }
+            }
+            _leaf => self.advance(),
+        }
+    }
+
+    /// After a leaf (or a just-closed collection) has been written, find the next value to visit:
+    /// the next sibling in the innermost frame, or (if that frame is exhausted) pop it and
+    /// continue with its parent.
+    fn advance(&mut self) -> Option<&'a Value> {
+        while let Some(frame) = self.frames.last_mut() {
+            match frame {
+                Remaining::ConsCar(cdr) => {
+                    let cdr = *cdr;
+                    *self.frames.last_mut().unwrap() = Remaining::ConsCdr(cdr);
+                    return Some(cdr);
+                }
+                Remaining::ConsCdr(_) => {
+                    self.frames.pop();
+                }
+                Remaining::Vector(iter) => {
+                    if let Some(next) = iter.next() {
+                        return Some(next);
+                    }
+                    self.frames.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MaxDepth, OpenStack, WriteStack};
+    use crate::Value;
+
+    // Builds `(a (1 2) b)` the way a reader would: open, push, open, push, push, close, push,
+    // close.
+    #[test]
+    fn open_stack_builds_nested_list() {
+        let mut stack = OpenStack::default();
+        stack.open_list().unwrap();
+        assert_eq!(stack.push_value(Value::symbol("a")), None);
+        stack.open_list().unwrap();
+        assert_eq!(stack.push_value(Value::from(1)), None);
+        assert_eq!(stack.push_value(Value::from(2)), None);
+        assert!(stack.close().is_none()); // `(1 2)` appended to the outer list, not yet complete.
+        assert_eq!(stack.push_value(Value::symbol("b")), None);
+        let result = stack.close().unwrap();
+
+        assert_eq!(
+            result,
+            Value::list(vec![
+                Value::symbol("a"),
+                Value::list(vec![Value::from(1), Value::from(2)]),
+                Value::symbol("b"),
+            ])
+        );
+    }
+
+    #[test]
+    fn open_stack_respects_max_depth() {
+        let mut stack = OpenStack::with_max_depth(Some(MaxDepth(1)));
+        stack.open_list().unwrap();
+        assert!(stack.open_list().is_err());
+    }
+
+    // Walks `(1 . 2)` and a two-element vector the way a writer would, visiting every leaf in
+    // order without recursing.
+    #[test]
+    fn write_stack_visits_leaves_in_order() {
+        let cons = Value::append(vec![Value::from(1)], Value::from(2));
+        let mut writer = WriteStack::new();
+        let mut visited = vec![&cons];
+        let mut current = &cons;
+        while let Some(next) = writer.step(current) {
+            visited.push(next);
+            current = next;
+        }
+        assert_eq!(
+            visited,
+            vec![&cons, &Value::from(1), &Value::from(2)]
+        );
+
+        let vector = Value::vector(vec![Value::from(1), Value::from(2)]);
+        let mut writer = WriteStack::new();
+        let mut visited = Vec::new();
+        let mut current = &vector;
+        loop {
+            match writer.step(current) {
+                Some(next) => {
+                    visited.push(next);
+                    current = next;
+                }
+                None => break,
+            }
+        }
+        assert_eq!(visited, vec![&Value::from(1), &Value::from(2)]);
+    }
+
+    // An empty vector has no children of its own to push a frame for, but it must still fall
+    // back to `advance()` so outer siblings (here, the `5` cdr) still get visited.
+    #[test]
+    fn write_stack_empty_vector_does_not_abandon_siblings() {
+        let inner = Value::append(vec![Value::vector(vec![])], Value::from(5));
+        let mut writer = WriteStack::new();
+        let mut visited = Vec::new();
+        let mut current = &inner;
+        while let Some(next) = writer.step(current) {
+            visited.push(next);
+            current = next;
+        }
+        assert_eq!(visited, vec![&Value::vector(vec![]), &Value::from(5)]);
+    }
+}
@@ -0,0 +1,25 @@
+//! Support for the `big_stack` feature: an alternative to the manual, mutate-without-recursing
+//! strategies of [`mod@crate::drop`] and [`mod@crate::clone`], for callers who'd rather the stack
+//! itself grow to accommodate deep `Value`s than remember to opt in to those.
+//!
+//! When `big_stack` is enabled, the recursive descents in the derived/natural recursive code
+//! paths are wrapped with [`ensure_sufficient_stack`], which checks the remaining stack space
+//! against a red zone and transparently allocates a fresh stack segment when it's running low,
+//! instead of overflowing.  So far that's [`crate::clone`]'s recursive `Clone` alternative and
+//! (short of the wrapper described on [`crate::drop::BigStackValueDropper`]) `Drop`; wiring this
+//! into `Debug` formatting and serde as well is still to do, since neither lives in this snapshot.
+
+/// The amount of remaining stack space, below which [`ensure_sufficient_stack`] allocates a new
+/// stack segment before proceeding.
+const RED_ZONE: usize = 32 * 1024;
+
+/// The size of each newly allocated stack segment.
+const STACK_SEGMENT_SIZE: usize = 1024 * 1024;
+
+/// Run `f`, first growing the stack if the remaining space is below [`RED_ZONE`].
+///
+/// Wrap recursive descents (one call per level of nesting) in this so that deep `Value`s
+/// dynamically get more stack instead of overflowing it.
+pub(crate) fn ensure_sufficient_stack<R>(f: impl FnOnce() -> R) -> R {
+    stacker::maybe_grow(RED_ZONE, STACK_SEGMENT_SIZE, f)
+}
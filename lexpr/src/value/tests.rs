@@ -139,6 +139,39 @@ fn test_vectors() {
     }
 }
 
+#[test]
+fn test_cons_ordering() {
+    use std::cmp::Ordering;
+
+    let one_two = Value::append(vec![Value::from(1)], Value::from(2));
+    let two_one = Value::append(vec![Value::from(2)], Value::from(1));
+    // Car is compared before cdr: 1 < 2, so `one_two` is less, regardless of the cdrs.
+    assert_eq!(one_two.partial_cmp(&two_one), Some(Ordering::Less));
+    assert_eq!(two_one.partial_cmp(&one_two), Some(Ordering::Greater));
+
+    let short = Value::vector(vec![Value::from(1), Value::from(9)]);
+    let long = Value::vector(vec![Value::from(1), Value::from(2), Value::from(0)]);
+    // First elements are compared before later ones: 1 == 1, then 9 > 2.
+    assert_eq!(short.partial_cmp(&long), Some(Ordering::Greater));
+}
+
+#[cfg(feature = "deep_safe_clone")]
+#[test]
+fn test_clone_preserves_structure() {
+    let dotted = Value::append(vec![Value::from(1), Value::from(2)], Value::from(3));
+    assert_eq!(dotted.clone(), dotted);
+    assert_eq!(
+        dotted.as_cons().map(Cons::to_vec),
+        dotted.clone().as_cons().map(Cons::to_vec)
+    );
+
+    let nested = Value::vector(vec![
+        Value::list(vec![Value::symbol("a"), Value::from(1)]),
+        Value::vector(vec![Value::from(2), Value::from(3)]),
+    ]);
+    assert_eq!(nested.clone(), nested);
+}
+
 #[test]
 fn drop_long_list() {
     let _long = Value::list(iter::repeat(Value::from(42)).take(1_000_000));
@@ -192,3 +225,39 @@ fn test_drop_prevent_stack_overflow() {
         drop(wrapped);
     });
 }
+
+#[cfg(feature = "deep_safe_clone")]
+#[test]
+fn test_clone_prevent_stack_overflow() {
+    with_deep_tree_on_small_stack("test_clone_prevent_stack_overflow", |deep_tree| {
+        let cloned = deep_tree.clone();
+        // Dropping these deep trees is not what this test is about, and could itself overflow
+        // the small stack depending on which other features are enabled; just leak them.
+        std::mem::forget(deep_tree);
+        std::mem::forget(cloned);
+    });
+}
+
+// Exercises the `big_stack` feature's alternative `Clone` impl (which only applies when
+// `deep_safe_clone` isn't also enabled, in which case that one wins instead -- see
+// `clone_growing_stack_as_needed`'s `#[cfg]`).
+#[cfg(all(feature = "big_stack", not(feature = "deep_safe_clone")))]
+#[test]
+fn test_clone_big_stack_prevent_stack_overflow() {
+    with_deep_tree_on_small_stack("test_clone_big_stack_prevent_stack_overflow", |deep_tree| {
+        let cloned = deep_tree.clone();
+        std::mem::forget(deep_tree);
+        std::mem::forget(cloned);
+    });
+}
+
+// Exercises `BigStackValueDropper`, the `big_stack` feature's wrapper for stack-safe dropping.
+#[cfg(feature = "big_stack")]
+#[test]
+fn test_drop_big_stack_prevent_stack_overflow() {
+    use crate::BigStackValueDropper;
+
+    with_deep_tree_on_small_stack("test_drop_big_stack_prevent_stack_overflow", |deep_tree| {
+        drop(BigStackValueDropper(deep_tree));
+    });
+}